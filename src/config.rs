@@ -0,0 +1,298 @@
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::policy::Policy;
+use crate::template::TemplateConfig;
+
+/// Typed configuration for the watcher. Values are read from an optional
+/// config file (TOML/YAML/JSON) and overridden by environment variables;
+/// secrets always prefer the environment.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Human-readable environment name surfaced in notifications.
+    pub env: String,
+    /// Namespaces whose deployments are never checked.
+    pub ignored_namespaces: HashSet<String>,
+    /// Notification targets, as shoutrrr-style `scheme://...` URLs.
+    pub notify_urls: Vec<String>,
+    /// How long a still-unresolved violation waits before re-alerting, in
+    /// seconds. `None` (or `0`) suppresses repeat alerts entirely.
+    pub repeat_interval_secs: Option<u64>,
+    /// Named receivers, each a set of notification target URLs. When empty,
+    /// `notify_urls` defines an implicit `default` receiver.
+    pub receivers: HashMap<String, Vec<String>>,
+    /// Routing table evaluated in order; unmatched violations fall through to
+    /// `default_receiver`.
+    pub routes: Vec<Route>,
+    /// Receiver used when no route matches.
+    pub default_receiver: String,
+    /// Keys the `InitDone` batch is grouped by, one message per group.
+    pub group_by: Vec<String>,
+    /// Message template overrides. Unset fields use the built-in defaults.
+    pub templates: TemplateConfig,
+    /// Pod-spec policies evaluated against each deployment. Empty means the
+    /// historical single nodeSelector check.
+    pub policies: Vec<Policy>,
+}
+
+/// One routing rule. A violation matches when every specified matcher matches;
+/// matching sends it to `receiver`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Route {
+    /// Receiver name to deliver matching violations to.
+    pub receiver: String,
+    /// Exact namespace match.
+    pub namespace: Option<String>,
+    /// Regex namespace match.
+    pub namespace_regex: Option<String>,
+    /// Exact deployment name match.
+    pub name: Option<String>,
+    /// Regex deployment name match.
+    pub name_regex: Option<String>,
+    /// Deployment labels that must all be present with the given values.
+    pub labels: HashMap<String, String>,
+    /// Keep evaluating later routes after this one matches.
+    #[serde(rename = "continue")]
+    pub continue_: bool,
+}
+
+/// Figure out the config file format from its extension.
+fn deserialize_file(path: &Path, contents: &str) -> Result<Config> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let config = match ext.as_str() {
+        "toml" => toml::from_str(contents).context("parsing TOML config")?,
+        "yaml" | "yml" => serde_yaml::from_str(contents).context("parsing YAML config")?,
+        "json" => serde_json::from_str(contents).context("parsing JSON config")?,
+        other => bail!("unsupported config format: .{}", other),
+    };
+    Ok(config)
+}
+
+impl Config {
+    /// Load configuration, layering environment overrides on top of an optional
+    /// config file. The file path comes from `--config <path>` or `CONFIG_PATH`.
+    pub fn load() -> Result<Self> {
+        let path = config_path();
+        let mut config = match &path {
+            Some(path) => {
+                let contents = std::fs::read_to_string(path)
+                    .with_context(|| format!("reading config file {}", path))?;
+                deserialize_file(Path::new(path), &contents)?
+            }
+            None => Config::default(),
+        };
+        config.apply_env_overrides();
+        config.normalize();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Fill in defaults and derive the implicit `default` receiver from the
+    /// top-level `notify_urls` when no receivers are declared explicitly.
+    fn normalize(&mut self) {
+        if self.default_receiver.is_empty() {
+            self.default_receiver = "default".to_string();
+        }
+        if self.group_by.is_empty() {
+            self.group_by = vec!["namespace".to_string()];
+        }
+        if self.receivers.is_empty() && !self.notify_urls.is_empty() {
+            self.receivers
+                .insert(self.default_receiver.clone(), self.notify_urls.clone());
+        }
+    }
+
+    /// Environment variables win over the file for each key they set. Keys
+    /// with no environment variable keep the file's value, so notification
+    /// URLs may come from the file when `NOTIFY_URL` is unset.
+    fn apply_env_overrides(&mut self) {
+        if let Ok(env_name) = env::var("ENV") {
+            self.env = env_name;
+        } else if self.env.is_empty() {
+            self.env = "unknown".to_string();
+        }
+
+        if let Ok(raw) = env::var("IGNORED_NAMESPACES") {
+            self.ignored_namespaces = split_list(&raw).into_iter().collect();
+        }
+
+        if let Ok(raw) = env::var("NOTIFY_URL") {
+            self.notify_urls = split_list(&raw);
+        } else if self.notify_urls.is_empty() {
+            // Legacy single-webhook configuration.
+            if let Ok(url) = env::var("SLACK_WEBHOOK_URL") {
+                self.notify_urls = vec![url.replace("https://", "slack://")];
+            }
+        }
+
+        if let Ok(raw) = env::var("REPEAT_INTERVAL") {
+            self.repeat_interval_secs = raw.parse().ok();
+        }
+    }
+
+    /// The configured repeat interval as a `Duration`, if re-alerting is on.
+    pub fn repeat_interval(&self) -> Option<std::time::Duration> {
+        self.repeat_interval_secs
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.receivers.is_empty() {
+            bail!("no notification targets configured (set NOTIFY_URL, notify_urls or receivers)");
+        }
+        if !self.receivers.contains_key(&self.default_receiver) {
+            bail!("default receiver '{}' is not defined", self.default_receiver);
+        }
+        for route in &self.routes {
+            if !self.receivers.contains_key(&route.receiver) {
+                bail!("route references undefined receiver '{}'", route.receiver);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Resolve the config file path from `--config <path>` or `CONFIG_PATH`.
+fn config_path() -> Option<String> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            return args.next();
+        }
+        if let Some(path) = arg.strip_prefix("--config=") {
+            return Some(path.to_string());
+        }
+    }
+    env::var("CONFIG_PATH").ok()
+}
+
+fn split_list(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // Tests that mutate process environment must not run concurrently.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    const ENV_KEYS: &[&str] = &[
+        "ENV",
+        "IGNORED_NAMESPACES",
+        "NOTIFY_URL",
+        "SLACK_WEBHOOK_URL",
+        "REPEAT_INTERVAL",
+    ];
+
+    fn clear_env() {
+        for key in ENV_KEYS {
+            env::remove_var(key);
+        }
+    }
+
+    fn receiver_config(url: &str) -> Config {
+        Config {
+            notify_urls: vec![url.to_string()],
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn split_list_trims_and_drops_empties() {
+        assert_eq!(split_list("a, b ,,c"), vec!["a", "b", "c"]);
+        assert!(split_list("  ").is_empty());
+    }
+
+    #[test]
+    fn deserialize_file_agrees_across_formats() {
+        let toml = deserialize_file(Path::new("c.toml"), "env = \"prod\"").unwrap();
+        let yaml = deserialize_file(Path::new("c.yaml"), "env: prod").unwrap();
+        let json = deserialize_file(Path::new("c.json"), "{\"env\": \"prod\"}").unwrap();
+        assert_eq!(toml.env, "prod");
+        assert_eq!(yaml.env, "prod");
+        assert_eq!(json.env, "prod");
+    }
+
+    #[test]
+    fn deserialize_file_rejects_unknown_format() {
+        assert!(deserialize_file(Path::new("c.ini"), "").is_err());
+    }
+
+    #[test]
+    fn normalize_fills_defaults_and_derives_receiver() {
+        let mut config = receiver_config("slack://hooks.slack.com/services/a/b/c");
+        config.normalize();
+        assert_eq!(config.default_receiver, "default");
+        assert_eq!(config.group_by, vec!["namespace"]);
+        assert_eq!(
+            config.receivers.get("default"),
+            Some(&vec!["slack://hooks.slack.com/services/a/b/c".to_string()])
+        );
+    }
+
+    #[test]
+    fn validate_rejects_missing_and_dangling_receivers() {
+        assert!(Config::default().validate().is_err());
+
+        let mut config = receiver_config("log://");
+        config.normalize();
+        config.routes.push(Route {
+            receiver: "nope".to_string(),
+            ..Route::default()
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn env_overrides_win_over_file_values() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        env::set_var("ENV", "prod");
+        env::set_var("IGNORED_NAMESPACES", "kube-system, default");
+        env::set_var("NOTIFY_URL", "log://");
+
+        let mut config = Config {
+            env: "from-file".to_string(),
+            notify_urls: vec!["slack://hooks.slack.com/services/a/b/c".to_string()],
+            ..Config::default()
+        };
+        config.apply_env_overrides();
+
+        assert_eq!(config.env, "prod");
+        assert_eq!(config.notify_urls, vec!["log://"]);
+        assert!(config.ignored_namespaces.contains("kube-system"));
+        clear_env();
+    }
+
+    #[test]
+    fn legacy_slack_webhook_is_rewritten_to_scheme() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        env::set_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/services/a/b/c");
+
+        let mut config = Config::default();
+        config.apply_env_overrides();
+
+        assert_eq!(
+            config.notify_urls,
+            vec!["slack://hooks.slack.com/services/a/b/c".to_string()]
+        );
+        clear_env();
+    }
+}
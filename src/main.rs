@@ -1,5 +1,4 @@
-use std::collections::HashSet;
-use std::env;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use futures::TryStreamExt;
 use k8s_openapi::api::apps::v1::Deployment;
@@ -8,90 +7,64 @@ use kube::{
     api::Api,
     runtime::watcher::{self, Event},
 };
-use serde::Serialize;
-use tracing::{error, info, warn};
+use tracing::{info, warn};
 
-#[derive(Serialize)]
-struct SlackMessage {
-    text: String,
-}
+mod config;
+mod notify;
+mod policy;
+mod route;
+mod state;
+mod template;
 
-async fn send_slack_notification(
-    webhook_url: &str,
-    env_name: &str,
-    deployment_name: &str,
-) -> Result<(), reqwest::Error> {
-    let client = reqwest::Client::new();
-    let message = SlackMessage {
-        text: format!(
-            "⚠️ Deployment missing nodeSelector\nenv: {}\nname: {}",
-            env_name, deployment_name
-        ),
-    };
-
-    client.post(webhook_url).json(&message).send().await?;
-
-    info!(
-        "Sent Slack notification for deployment: {}",
-        deployment_name
-    );
-    Ok(())
-}
+use std::sync::Arc;
 
-async fn send_slack_batch_notification(
-    webhook_url: &str,
-    env_name: &str,
-    deployments: &[(String, String)],
-) -> Result<(), reqwest::Error> {
-    if deployments.is_empty() {
-        return Ok(());
-    }
+use config::Config;
+use notify::{Notifiers, ViolationEvent};
+use policy::PolicyEngine;
+use route::Router;
+use state::{Transition, ViolationState};
+use template::Templates;
 
-    let client = reqwest::Client::new();
-    let deployment_list: Vec<String> = deployments
-        .iter()
-        .map(|(ns, name)| format!("• {}/{}", ns, name))
-        .collect();
-
-    let message = SlackMessage {
-        text: format!(
-            "⚠️ Found {} deployment(s) missing nodeSelector\nenv: {}\n{}",
-            deployments.len(),
-            env_name,
-            deployment_list.join("\n")
-        ),
-    };
-
-    client.post(webhook_url).json(&message).send().await?;
-
-    info!(
-        "Sent batch Slack notification for {} deployments",
-        deployments.len()
-    );
-    Ok(())
+fn should_ignore_namespace(namespace: &str, ignored: &HashSet<String>) -> bool {
+    ignored.contains(namespace)
 }
 
-fn has_node_selector(deployment: &Deployment) -> bool {
-    deployment
-        .spec
-        .as_ref()
-        .and_then(|spec| spec.template.spec.as_ref())
-        .and_then(|pod_spec| pod_spec.node_selector.as_ref())
-        .map(|ns| !ns.is_empty())
-        .unwrap_or(false)
+/// A deployment's labels as a plain map, for routing and grouping.
+fn deployment_labels(deployment: &Deployment) -> BTreeMap<String, String> {
+    deployment.metadata.labels.clone().unwrap_or_default()
 }
 
-fn parse_ignored_namespaces() -> HashSet<String> {
-    env::var("IGNORED_NAMESPACES")
-        .unwrap_or_default()
-        .split(',')
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect()
+/// The value a violation groups under for the batch notification. `namespace`
+/// and `name` are built-in; any other key is looked up in the labels.
+fn group_value(event: &ViolationEvent, group_by: &[String]) -> String {
+    group_by
+        .iter()
+        .map(|key| match key.as_str() {
+            "namespace" => event.namespace.clone(),
+            "name" => event.name.clone(),
+            other => event.labels.get(other).cloned().unwrap_or_default(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
-fn should_ignore_namespace(namespace: &str, ignored: &HashSet<String>) -> bool {
-    ignored.contains(namespace)
+/// Deliver a violation to every receiver it routes to.
+async fn dispatch(
+    receivers: &HashMap<String, Notifiers>,
+    router: &Router,
+    event: &ViolationEvent,
+    resolved: bool,
+) {
+    for name in router.receivers_for(event) {
+        let Some(notifiers) = receivers.get(&name) else {
+            continue;
+        };
+        if resolved {
+            notifiers.notify_resolved(event).await;
+        } else {
+            notifiers.notify(event).await;
+        }
+    }
 }
 
 #[tokio::main]
@@ -101,10 +74,19 @@ async fn main() -> anyhow::Result<()> {
 
     tracing_subscriber::fmt::init();
 
-    let slack_webhook_url =
-        env::var("SLACK_WEBHOOK_URL").expect("SLACK_WEBHOOK_URL environment variable must be set");
-    let env_name = env::var("ENV").unwrap_or_else(|_| "unknown".to_string());
-    let ignored_namespaces = parse_ignored_namespaces();
+    let config = Config::load()?;
+    let env_name = config.env.clone();
+    let ignored_namespaces = config.ignored_namespaces.clone();
+    let group_by = config.group_by.clone();
+    let templates = Arc::new(Templates::new(&config.templates)?);
+    let receivers: HashMap<String, Notifiers> = config
+        .receivers
+        .iter()
+        .map(|(name, urls)| Ok((name.clone(), Notifiers::from_urls(urls, templates.clone())?)))
+        .collect::<anyhow::Result<_>>()?;
+    let router = Router::new(&config.routes, config.default_receiver.clone())?;
+    let engine = PolicyEngine::new(config.policies.clone());
+    let mut violation_state = ViolationState::new(config.repeat_interval());
 
     info!("Starting nodeselector-notify for env: {}", env_name);
     if !ignored_namespaces.is_empty() {
@@ -118,7 +100,7 @@ async fn main() -> anyhow::Result<()> {
 
     futures::pin_mut!(watcher);
 
-    let mut init_violations: Vec<(String, String)> = Vec::new();
+    let mut init_violations: Vec<ViolationEvent> = Vec::new();
 
     while let Some(event) = watcher.try_next().await? {
         match event {
@@ -134,20 +116,47 @@ async fn main() -> anyhow::Result<()> {
                     continue;
                 }
 
-                if !has_node_selector(&deployment) {
-                    warn!("Deployment {}/{} has no nodeSelector", namespace, name);
-                    if let Err(e) =
-                        send_slack_notification(&slack_webhook_url, &env_name, name).await
-                    {
-                        error!("Failed to send Slack notification: {}", e);
+                let failures = engine.evaluate(&deployment);
+                let violating = !failures.is_empty();
+                match violation_state.observe(namespace, name, violating) {
+                    Transition::Alert => {
+                        warn!(
+                            "Deployment {}/{} failed policies: {:?}",
+                            namespace, name, failures
+                        );
+                        let event = ViolationEvent::new(namespace, name, &env_name)
+                            .with_labels(deployment_labels(&deployment))
+                            .with_policies(failures);
+                        dispatch(&receivers, &router, &event, false).await;
+                    }
+                    Transition::Resolved => {
+                        info!("Deployment {}/{} policy checks passing", namespace, name);
+                        let event = ViolationEvent::new(namespace, name, &env_name)
+                            .with_labels(deployment_labels(&deployment));
+                        dispatch(&receivers, &router, &event, true).await;
+                    }
+                    Transition::Unchanged => {
+                        if violating {
+                            info!("Deployment {}/{} still violating", namespace, name);
+                        } else {
+                            info!("Deployment {}/{} passes policy checks", namespace, name);
+                        }
                     }
-                } else {
-                    info!("Deployment {}/{} has nodeSelector set", namespace, name);
                 }
             }
             Event::Delete(deployment) => {
                 let name = deployment.metadata.name.as_deref().unwrap_or("unknown");
+                let namespace = deployment
+                    .metadata
+                    .namespace
+                    .as_deref()
+                    .unwrap_or("default");
                 info!("Deployment deleted: {}", name);
+                if violation_state.clear(namespace, name) {
+                    let event = ViolationEvent::new(namespace, name, &env_name)
+                        .with_labels(deployment_labels(&deployment));
+                    dispatch(&receivers, &router, &event, true).await;
+                }
             }
             Event::Init => {
                 info!("Watcher initializing, collecting deployments");
@@ -165,9 +174,20 @@ async fn main() -> anyhow::Result<()> {
                     continue;
                 }
 
-                if !has_node_selector(&deployment) {
-                    warn!("Deployment {}/{} has no nodeSelector", namespace, name);
-                    init_violations.push((namespace.to_string(), name.to_string()));
+                let failures = engine.evaluate(&deployment);
+                if !failures.is_empty() {
+                    warn!(
+                        "Deployment {}/{} failed policies: {:?}",
+                        namespace, name, failures
+                    );
+                    // Seed the state map so the batch notice isn't immediately
+                    // followed by a duplicate single alert on the next Apply.
+                    violation_state.observe(namespace, name, true);
+                    init_violations.push(
+                        ViolationEvent::new(namespace, name, &env_name)
+                            .with_labels(deployment_labels(&deployment))
+                            .with_policies(failures),
+                    );
                 }
             }
             Event::InitDone => {
@@ -176,11 +196,29 @@ async fn main() -> anyhow::Result<()> {
                     init_violations.len()
                 );
 
-                if let Err(e) =
-                    send_slack_batch_notification(&slack_webhook_url, &env_name, &init_violations)
-                        .await
-                {
-                    error!("Failed to send batch Slack notification: {}", e);
+                // Route each violation, then split per receiver into one batch
+                // message per `group_by` group.
+                let mut grouped: HashMap<String, BTreeMap<String, Vec<ViolationEvent>>> =
+                    HashMap::new();
+                for event in &init_violations {
+                    let group = group_value(event, &group_by);
+                    for receiver in router.receivers_for(event) {
+                        grouped
+                            .entry(receiver)
+                            .or_default()
+                            .entry(group.clone())
+                            .or_default()
+                            .push(event.clone());
+                    }
+                }
+
+                for (receiver, groups) in grouped {
+                    let Some(notifiers) = receivers.get(&receiver) else {
+                        continue;
+                    };
+                    for violations in groups.values() {
+                        notifiers.notify_batch(&env_name, violations).await;
+                    }
                 }
 
                 init_violations.clear();
@@ -0,0 +1,422 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result, anyhow, bail};
+use async_trait::async_trait;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{error, info};
+
+use crate::template::Templates;
+
+/// A single deployment reported in a notification: one that failed policy
+/// checks, or one whose violation resolved.
+#[derive(Debug, Clone)]
+pub struct ViolationEvent {
+    pub namespace: String,
+    pub name: String,
+    pub env: String,
+    pub labels: BTreeMap<String, String>,
+    /// Descriptions of the policies this deployment failed.
+    pub policies: Vec<String>,
+}
+
+impl ViolationEvent {
+    pub fn new(
+        namespace: impl Into<String>,
+        name: impl Into<String>,
+        env: impl Into<String>,
+    ) -> Self {
+        Self {
+            namespace: namespace.into(),
+            name: name.into(),
+            env: env.into(),
+            labels: BTreeMap::new(),
+            policies: Vec::new(),
+        }
+    }
+
+    /// Attach the deployment's labels, used for routing and grouping.
+    pub fn with_labels(mut self, labels: BTreeMap<String, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Attach the descriptions of the policies the deployment failed.
+    pub fn with_policies(mut self, policies: Vec<String>) -> Self {
+        self.policies = policies;
+        self
+    }
+}
+
+/// A fully-rendered notification ready to be delivered by a backend.
+pub struct Message {
+    pub text: String,
+    /// `(namespace, name)` of the deployment, used by stateful backends to edit
+    /// a prior message in place. `None` for batch notifications.
+    pub key: Option<(String, String)>,
+    /// Whether this message marks a violation as resolved.
+    pub resolved: bool,
+}
+
+#[derive(Serialize)]
+struct TextMessage<'a> {
+    text: &'a str,
+}
+
+/// A notification backend. Each configured `NOTIFY_URL` resolves to one
+/// implementation, selected by the URL scheme.
+#[async_trait]
+trait Notifier: Send + Sync {
+    async fn send(&self, message: &Message) -> Result<()>;
+}
+
+/// Posts a rendered message to a webhook. Shared by the Mattermost, Teams and
+/// generic backends; Slack uses `SlackWebhookNotifier` for richer blocks.
+struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+    backend: &'static str,
+}
+
+impl WebhookNotifier {
+    fn new(url: String, backend: &'static str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            backend,
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn send(&self, message: &Message) -> Result<()> {
+        let body = TextMessage { text: &message.text };
+        self.client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .with_context(|| format!("posting to {} webhook", self.backend))?
+            .error_for_status()
+            .with_context(|| format!("{} webhook returned an error", self.backend))?;
+        info!("Sent {} notification", self.backend);
+        Ok(())
+    }
+}
+
+/// The Slack incoming-webhook backend. Sends the rendered text inside a Block
+/// Kit section so alerts render richly in the channel.
+struct SlackWebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+#[async_trait]
+impl Notifier for SlackWebhookNotifier {
+    async fn send(&self, message: &Message) -> Result<()> {
+        let body = json!({
+            "text": message.text,
+            "blocks": [{
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": message.text },
+            }],
+        });
+        self.client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .context("posting to Slack webhook")?
+            .error_for_status()
+            .context("Slack webhook returned an error")?;
+        info!("Sent Slack notification");
+        Ok(())
+    }
+}
+
+/// A posted Slack message we may later edit in place.
+struct PostedMessage {
+    ts: String,
+    text: String,
+}
+
+/// Slack Web API response for `chat.postMessage` / `chat.update`.
+#[derive(Deserialize)]
+struct SlackApiResponse {
+    ok: bool,
+    ts: Option<String>,
+    error: Option<String>,
+}
+
+/// The Slack Web API backend. Posts with `chat.postMessage` and keeps the
+/// returned `ts` keyed by `(namespace, name)`, so a persisting or resolving
+/// violation edits the original message via `chat.update` instead of posting a
+/// duplicate, keeping the channel tidy.
+struct SlackApiNotifier {
+    client: reqwest::Client,
+    token: String,
+    channel: String,
+    posted: Mutex<HashMap<(String, String), PostedMessage>>,
+}
+
+impl SlackApiNotifier {
+    async fn call(&self, method: &str, body: serde_json::Value) -> Result<SlackApiResponse> {
+        let resp: SlackApiResponse = self
+            .client
+            .post(format!("https://slack.com/api/{}", method))
+            .bearer_auth(&self.token)
+            .json(&body)
+            .send()
+            .await
+            .with_context(|| format!("calling Slack {}", method))?
+            .json()
+            .await
+            .with_context(|| format!("parsing Slack {} response", method))?;
+        if !resp.ok {
+            bail!(
+                "Slack {} failed: {}",
+                method,
+                resp.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+        Ok(resp)
+    }
+
+    async fn post(&self, text: &str) -> Result<Option<String>> {
+        let resp = self
+            .call(
+                "chat.postMessage",
+                json!({ "channel": self.channel, "text": text }),
+            )
+            .await?;
+        Ok(resp.ts)
+    }
+
+    async fn update(&self, ts: &str, text: &str) -> Result<()> {
+        self.call(
+            "chat.update",
+            json!({ "channel": self.channel, "ts": ts, "text": text }),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Notifier for SlackApiNotifier {
+    async fn send(&self, message: &Message) -> Result<()> {
+        // Batch notifications have no per-deployment key; always post fresh.
+        let Some(key) = message.key.clone() else {
+            self.post(&message.text).await?;
+            info!("Sent Slack batch message");
+            return Ok(());
+        };
+
+        let existing = {
+            let posted = self.posted.lock().unwrap();
+            posted.get(&key).map(|p| (p.ts.clone(), p.text.clone()))
+        };
+
+        match (existing, message.resolved) {
+            // Resolving a tracked violation: strike the original and mark it ✅.
+            (Some((ts, original)), true) => {
+                let text = format!("~{}~\n{}", original, message.text);
+                self.update(&ts, &text).await?;
+                self.posted.lock().unwrap().remove(&key);
+                info!("Updated Slack message {} as resolved", ts);
+            }
+            // Persisting violation: edit the existing message in place.
+            (Some((ts, _)), false) => {
+                self.update(&ts, &message.text).await?;
+                self.posted.lock().unwrap().insert(
+                    key,
+                    PostedMessage {
+                        ts,
+                        text: message.text.clone(),
+                    },
+                );
+                info!("Updated existing Slack message in place");
+            }
+            // Resolved with nothing tracked: just post the notice.
+            (None, true) => {
+                self.post(&message.text).await?;
+                info!("Sent Slack resolved message");
+            }
+            // New violation: post and remember the ts for later edits.
+            (None, false) => {
+                if let Some(ts) = self.post(&message.text).await? {
+                    self.posted.lock().unwrap().insert(
+                        key,
+                        PostedMessage {
+                            ts,
+                            text: message.text.clone(),
+                        },
+                    );
+                }
+                info!("Sent Slack message");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A sink that logs notifications instead of sending them. Useful for local
+/// runs and for operators who just want the violations in their log pipeline.
+struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    async fn send(&self, message: &Message) -> Result<()> {
+        info!("{}", message.text.replace('\n', " "));
+        Ok(())
+    }
+}
+
+/// Validate a Slack incoming-webhook URL so we fail fast on a typo instead of
+/// silently dropping every alert.
+fn validate_slack_webhook(url: &str) -> Result<()> {
+    let rest = url
+        .strip_prefix("https://")
+        .ok_or_else(|| anyhow!("Slack webhook must be https: {}", url))?;
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    if host != "hooks.slack.com" {
+        bail!("Slack webhook host must be hooks.slack.com, got {}", host);
+    }
+    if !path.starts_with("services/") || path.matches('/').count() < 3 {
+        bail!("malformed Slack webhook path: /{}", path);
+    }
+    Ok(())
+}
+
+/// Turn a `scheme://...` target into the https endpoint it addresses.
+fn https_endpoint(rest: &str) -> String {
+    format!("https://{}", rest)
+}
+
+/// Build a notifier from one `NOTIFY_URL`. The scheme selects the backend.
+fn build_notifier(url: &str) -> Result<Box<dyn Notifier>> {
+    let (scheme, rest) = url
+        .split_once("://")
+        .ok_or_else(|| anyhow!("notify url is missing a scheme: {}", url))?;
+
+    let client = || reqwest::Client::new();
+    let notifier: Box<dyn Notifier> = match scheme {
+        "slack" => {
+            let endpoint = https_endpoint(rest);
+            validate_slack_webhook(&endpoint)?;
+            Box::new(SlackWebhookNotifier {
+                client: client(),
+                url: endpoint,
+            })
+        }
+        "slackapi" => {
+            // slackapi://<bot-token>@<channel>
+            let (token, channel) = rest
+                .split_once('@')
+                .ok_or_else(|| anyhow!("slackapi url must be slackapi://<token>@<channel>"))?;
+            Box::new(SlackApiNotifier {
+                client: client(),
+                token: token.to_string(),
+                channel: channel.to_string(),
+                posted: Mutex::new(HashMap::new()),
+            })
+        }
+        "mattermost" => Box::new(WebhookNotifier::new(https_endpoint(rest), "Mattermost")),
+        "teams" => Box::new(WebhookNotifier::new(https_endpoint(rest), "Teams")),
+        "generic+https" => Box::new(WebhookNotifier::new(
+            format!("https://{}", rest),
+            "generic webhook",
+        )),
+        "generic+http" => Box::new(WebhookNotifier::new(
+            format!("http://{}", rest),
+            "generic webhook",
+        )),
+        "log" | "stdout" => Box::new(LogNotifier),
+        other => bail!("unknown notify url scheme: {}://", other),
+    };
+    Ok(notifier)
+}
+
+/// A fan-out over every configured backend. A single violation is rendered once
+/// and delivered to all targets concurrently; a failing target never blocks the
+/// others.
+pub struct Notifiers {
+    targets: Vec<Box<dyn Notifier>>,
+    templates: Arc<Templates>,
+}
+
+impl Notifiers {
+    /// Build the set of notifiers from the configured `NOTIFY_URL` values.
+    pub fn from_urls<I, S>(urls: I, templates: Arc<Templates>) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let targets = urls
+            .into_iter()
+            .map(|u| build_notifier(u.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        if targets.is_empty() {
+            bail!("no notification targets configured");
+        }
+        Ok(Self { targets, templates })
+    }
+
+    async fn fan_out(&self, message: Message) {
+        let sends = self.targets.iter().map(|t| t.send(&message));
+        for result in join_all(sends).await {
+            if let Err(e) = result {
+                error!("Failed to send notification: {:#}", e);
+            }
+        }
+    }
+
+    pub async fn notify(&self, event: &ViolationEvent) {
+        match self.templates.render_alert(event) {
+            Ok(text) => {
+                self.fan_out(Message {
+                    text,
+                    key: Some((event.namespace.clone(), event.name.clone())),
+                    resolved: false,
+                })
+                .await
+            }
+            Err(e) => error!("Failed to render alert: {:#}", e),
+        }
+    }
+
+    pub async fn notify_batch(&self, env: &str, violations: &[ViolationEvent]) {
+        if violations.is_empty() {
+            return;
+        }
+        match self.templates.render_batch(env, violations) {
+            Ok(text) => {
+                self.fan_out(Message {
+                    text,
+                    key: None,
+                    resolved: false,
+                })
+                .await
+            }
+            Err(e) => error!("Failed to render batch alert: {:#}", e),
+        }
+    }
+
+    pub async fn notify_resolved(&self, event: &ViolationEvent) {
+        match self.templates.render_resolved(event) {
+            Ok(text) => {
+                self.fan_out(Message {
+                    text,
+                    key: Some((event.namespace.clone(), event.name.clone())),
+                    resolved: true,
+                })
+                .await
+            }
+            Err(e) => error!("Failed to render resolved notice: {:#}", e),
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::PodSpec;
+use serde::Deserialize;
+
+/// A single pod-spec hygiene rule the tool enforces.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Rule {
+    /// `spec.template.spec.nodeSelector` must be set and non-empty.
+    NodeSelector,
+    /// At least one toleration must be declared.
+    Tolerations,
+    /// Every container must declare resource requests.
+    ResourceRequests,
+    /// Every container must declare resource limits.
+    ResourceLimits,
+    /// `spec.template.spec.affinity` must be present.
+    Affinity,
+    /// The deployment must carry all of these labels.
+    RequiredLabels { keys: Vec<String> },
+    /// The deployment must carry all of these annotations.
+    RequiredAnnotations { keys: Vec<String> },
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A configurable policy: a rule plus the metadata surfaced in notifications.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Policy {
+    /// Stable identifier, e.g. `require-node-selector`.
+    pub id: String,
+    /// Human-readable description included in the notification.
+    pub description: String,
+    /// Whether the policy is evaluated. Defaults to `true`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub rule: Rule,
+}
+
+/// The set of policies evaluated against every deployment.
+pub struct PolicyEngine {
+    policies: Vec<Policy>,
+}
+
+impl PolicyEngine {
+    /// Build the engine from the configured policies, falling back to the
+    /// historical single nodeSelector check when none are declared.
+    pub fn new(policies: Vec<Policy>) -> Self {
+        let policies = if policies.is_empty() {
+            vec![Policy {
+                id: "require-node-selector".to_string(),
+                description: "Deployment missing nodeSelector".to_string(),
+                enabled: true,
+                rule: Rule::NodeSelector,
+            }]
+        } else {
+            policies
+        };
+        Self { policies }
+    }
+
+    /// Descriptions of every enabled policy the deployment fails, in order.
+    pub fn evaluate(&self, deployment: &Deployment) -> Vec<String> {
+        self.policies
+            .iter()
+            .filter(|p| p.enabled)
+            .filter(|p| !passes(&p.rule, deployment))
+            .map(|p| p.description.clone())
+            .collect()
+    }
+}
+
+fn pod_spec(deployment: &Deployment) -> Option<&PodSpec> {
+    deployment
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.template.spec.as_ref())
+}
+
+/// Whether a deployment satisfies a single rule.
+fn passes(rule: &Rule, deployment: &Deployment) -> bool {
+    match rule {
+        Rule::NodeSelector => pod_spec(deployment)
+            .and_then(|s| s.node_selector.as_ref())
+            .is_some_and(|ns| !ns.is_empty()),
+        Rule::Tolerations => pod_spec(deployment)
+            .and_then(|s| s.tolerations.as_ref())
+            .is_some_and(|t| !t.is_empty()),
+        Rule::Affinity => pod_spec(deployment).is_some_and(|s| s.affinity.is_some()),
+        Rule::ResourceRequests => all_containers_have(deployment, |r| {
+            r.requests.as_ref().is_some_and(|m| !m.is_empty())
+        }),
+        Rule::ResourceLimits => all_containers_have(deployment, |r| {
+            r.limits.as_ref().is_some_and(|m| !m.is_empty())
+        }),
+        Rule::RequiredLabels { keys } => {
+            let labels = deployment.metadata.labels.as_ref();
+            keys.iter()
+                .all(|k| labels.is_some_and(|l| l.contains_key(k)))
+        }
+        Rule::RequiredAnnotations { keys } => {
+            let annotations = deployment.metadata.annotations.as_ref();
+            keys.iter()
+                .all(|k| annotations.is_some_and(|a| a.contains_key(k)))
+        }
+    }
+}
+
+/// Check that every container satisfies a predicate on its resource block.
+fn all_containers_have(
+    deployment: &Deployment,
+    check: impl Fn(&k8s_openapi::api::core::v1::ResourceRequirements) -> bool,
+) -> bool {
+    match pod_spec(deployment) {
+        Some(spec) if !spec.containers.is_empty() => spec
+            .containers
+            .iter()
+            .all(|c| c.resources.as_ref().is_some_and(&check)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::apps::v1::DeploymentSpec;
+    use k8s_openapi::api::core::v1::{
+        Affinity, Container, PodTemplateSpec, ResourceRequirements, Toleration,
+    };
+    use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+    use std::collections::BTreeMap;
+
+    /// Build a Deployment whose pod spec is produced by `f`.
+    fn deployment_with(f: impl FnOnce(&mut PodSpec)) -> Deployment {
+        let mut pod = PodSpec::default();
+        f(&mut pod);
+        Deployment {
+            spec: Some(DeploymentSpec {
+                template: PodTemplateSpec {
+                    spec: Some(pod),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn policy(rule: Rule) -> Policy {
+        Policy {
+            id: "p".to_string(),
+            description: "desc".to_string(),
+            enabled: true,
+            rule,
+        }
+    }
+
+    fn fails(rule: Rule, deployment: &Deployment) -> bool {
+        !passes(&rule, deployment)
+    }
+
+    #[test]
+    fn empty_policies_fall_back_to_node_selector_default() {
+        let engine = PolicyEngine::new(vec![]);
+        let missing = deployment_with(|s| s.containers.push(Container::default()));
+        assert_eq!(engine.evaluate(&missing), vec!["Deployment missing nodeSelector"]);
+
+        let ok = deployment_with(|s| {
+            s.node_selector =
+                Some(BTreeMap::from([("disk".to_string(), "ssd".to_string())]));
+        });
+        assert!(engine.evaluate(&ok).is_empty());
+    }
+
+    #[test]
+    fn node_selector_rule() {
+        assert!(fails(Rule::NodeSelector, &deployment_with(|_| {})));
+        let ok = deployment_with(|s| {
+            s.node_selector = Some(BTreeMap::from([("k".to_string(), "v".to_string())]));
+        });
+        assert!(!fails(Rule::NodeSelector, &ok));
+    }
+
+    #[test]
+    fn tolerations_rule() {
+        assert!(fails(Rule::Tolerations, &deployment_with(|_| {})));
+        let ok = deployment_with(|s| s.tolerations = Some(vec![Toleration::default()]));
+        assert!(!fails(Rule::Tolerations, &ok));
+    }
+
+    #[test]
+    fn affinity_rule() {
+        assert!(fails(Rule::Affinity, &deployment_with(|_| {})));
+        let ok = deployment_with(|s| s.affinity = Some(Affinity::default()));
+        assert!(!fails(Rule::Affinity, &ok));
+    }
+
+    #[test]
+    fn resource_requests_rule_requires_every_container() {
+        let qty = BTreeMap::from([("cpu".to_string(), Quantity("100m".to_string()))]);
+        let with_requests = Container {
+            resources: Some(ResourceRequirements {
+                requests: Some(qty.clone()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let ok = deployment_with(|s| s.containers = vec![with_requests.clone()]);
+        assert!(!fails(Rule::ResourceRequests, &ok));
+
+        // One bare container is enough to fail.
+        let mixed =
+            deployment_with(|s| s.containers = vec![with_requests, Container::default()]);
+        assert!(fails(Rule::ResourceRequests, &mixed));
+    }
+
+    #[test]
+    fn resource_limits_rule() {
+        let qty = BTreeMap::from([("cpu".to_string(), Quantity("100m".to_string()))]);
+        let ok = deployment_with(|s| {
+            s.containers = vec![Container {
+                resources: Some(ResourceRequirements {
+                    limits: Some(qty),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }];
+        });
+        assert!(!fails(Rule::ResourceLimits, &ok));
+        assert!(fails(Rule::ResourceLimits, &deployment_with(|_| {})));
+    }
+
+    #[test]
+    fn empty_containers_fails_resource_rules() {
+        // No containers means the requirement is vacuously unmet, not satisfied.
+        assert!(fails(Rule::ResourceRequests, &deployment_with(|_| {})));
+    }
+
+    #[test]
+    fn required_labels_and_annotations_check_deployment_metadata() {
+        let rule = Rule::RequiredLabels {
+            keys: vec!["owner".to_string()],
+        };
+        let mut deployment = deployment_with(|_| {});
+        assert!(fails(rule.clone(), &deployment));
+        deployment.metadata.labels =
+            Some(BTreeMap::from([("owner".to_string(), "team".to_string())]));
+        assert!(!fails(rule, &deployment));
+
+        let ann = Rule::RequiredAnnotations {
+            keys: vec!["runbook".to_string()],
+        };
+        assert!(fails(ann.clone(), &deployment_with(|_| {})));
+    }
+
+    #[test]
+    fn disabled_policies_are_skipped() {
+        let mut p = policy(Rule::NodeSelector);
+        p.enabled = false;
+        let engine = PolicyEngine::new(vec![p]);
+        assert!(engine.evaluate(&deployment_with(|_| {})).is_empty());
+    }
+}
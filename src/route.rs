@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+use crate::config::Route;
+use crate::notify::ViolationEvent;
+
+/// A route with its regex matchers pre-compiled.
+struct CompiledRoute {
+    receiver: String,
+    namespace: Option<String>,
+    namespace_regex: Option<Regex>,
+    name: Option<String>,
+    name_regex: Option<Regex>,
+    labels: BTreeMap<String, String>,
+    continue_: bool,
+}
+
+impl CompiledRoute {
+    fn matches(&self, namespace: &str, name: &str, labels: &BTreeMap<String, String>) -> bool {
+        if let Some(ns) = &self.namespace {
+            if ns != namespace {
+                return false;
+            }
+        }
+        if let Some(re) = &self.namespace_regex {
+            if !re.is_match(namespace) {
+                return false;
+            }
+        }
+        if let Some(n) = &self.name {
+            if n != name {
+                return false;
+            }
+        }
+        if let Some(re) = &self.name_regex {
+            if !re.is_match(name) {
+                return false;
+            }
+        }
+        self.labels
+            .iter()
+            .all(|(k, v)| labels.get(k).is_some_and(|actual| actual == v))
+    }
+}
+
+/// Alertmanager-style router: evaluates routes in order and returns the set of
+/// receivers a violation should be delivered to, falling through to a default.
+pub struct Router {
+    routes: Vec<CompiledRoute>,
+    default_receiver: String,
+}
+
+impl Router {
+    pub fn new(routes: &[Route], default_receiver: String) -> Result<Self> {
+        let routes = routes
+            .iter()
+            .map(|r| {
+                Ok(CompiledRoute {
+                    receiver: r.receiver.clone(),
+                    namespace: r.namespace.clone(),
+                    namespace_regex: compile_opt(r.namespace_regex.as_deref())?,
+                    name: r.name.clone(),
+                    name_regex: compile_opt(r.name_regex.as_deref())?,
+                    labels: r.labels.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+                    continue_: r.continue_,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            routes,
+            default_receiver,
+        })
+    }
+
+    /// Receivers a violation routes to, in configured order. A route with
+    /// `continue = true` lets evaluation proceed to later routes. When nothing
+    /// matches, the default receiver is used.
+    pub fn receivers_for(&self, event: &ViolationEvent) -> Vec<String> {
+        let mut matched = Vec::new();
+        for route in &self.routes {
+            if route.matches(&event.namespace, &event.name, &event.labels) {
+                if !matched.contains(&route.receiver) {
+                    matched.push(route.receiver.clone());
+                }
+                if !route.continue_ {
+                    break;
+                }
+            }
+        }
+        if matched.is_empty() {
+            matched.push(self.default_receiver.clone());
+        }
+        matched
+    }
+}
+
+fn compile_opt(pattern: Option<&str>) -> Result<Option<Regex>> {
+    pattern
+        .map(|p| Regex::new(p).with_context(|| format!("compiling route regex '{}'", p)))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(receiver: &str) -> Route {
+        Route {
+            receiver: receiver.to_string(),
+            ..Route::default()
+        }
+    }
+
+    fn event(namespace: &str, name: &str) -> ViolationEvent {
+        ViolationEvent::new(namespace, name, "test")
+    }
+
+    #[test]
+    fn falls_through_to_default_when_nothing_matches() {
+        let router = Router::new(&[], "default".to_string()).unwrap();
+        assert_eq!(router.receivers_for(&event("ns", "a")), vec!["default"]);
+    }
+
+    #[test]
+    fn exact_namespace_matcher_picks_receiver() {
+        let routes = vec![Route {
+            namespace: Some("team-a".to_string()),
+            ..route("team")
+        }];
+        let router = Router::new(&routes, "default".to_string()).unwrap();
+        assert_eq!(router.receivers_for(&event("team-a", "x")), vec!["team"]);
+        assert_eq!(router.receivers_for(&event("team-b", "x")), vec!["default"]);
+    }
+
+    #[test]
+    fn first_match_wins_without_continue() {
+        let routes = vec![
+            Route {
+                namespace_regex: Some("^team-".to_string()),
+                ..route("team")
+            },
+            Route {
+                namespace_regex: Some(".*".to_string()),
+                ..route("audit")
+            },
+        ];
+        let router = Router::new(&routes, "default".to_string()).unwrap();
+        assert_eq!(router.receivers_for(&event("team-a", "x")), vec!["team"]);
+    }
+
+    #[test]
+    fn continue_fans_out_to_multiple_receivers() {
+        let routes = vec![
+            Route {
+                namespace_regex: Some("^team-".to_string()),
+                continue_: true,
+                ..route("team")
+            },
+            Route {
+                namespace_regex: Some(".*".to_string()),
+                ..route("audit")
+            },
+        ];
+        let router = Router::new(&routes, "default".to_string()).unwrap();
+        assert_eq!(
+            router.receivers_for(&event("team-a", "x")),
+            vec!["team", "audit"]
+        );
+    }
+
+    #[test]
+    fn label_matcher_requires_all_labels() {
+        let mut labels = std::collections::HashMap::new();
+        labels.insert("tier".to_string(), "backend".to_string());
+        let routes = vec![Route {
+            labels,
+            ..route("backend")
+        }];
+        let router = Router::new(&routes, "default".to_string()).unwrap();
+
+        let mut ev = event("ns", "x");
+        ev.labels.insert("tier".to_string(), "backend".to_string());
+        assert_eq!(router.receivers_for(&ev), vec!["backend"]);
+
+        let mut other = event("ns", "x");
+        other.labels.insert("tier".to_string(), "frontend".to_string());
+        assert_eq!(router.receivers_for(&other), vec!["default"]);
+    }
+}
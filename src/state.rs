@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Tracks the violation status of every deployment so we only alert on state
+/// transitions, mirroring Alertmanager's resolve/repeat semantics.
+#[derive(Default)]
+pub struct ViolationState {
+    active: HashMap<(String, String), Instant>,
+    repeat_interval: Option<Duration>,
+}
+
+/// What the caller should do after observing a deployment's current status.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Transition {
+    /// The deployment is newly violating, or has been violating long enough to
+    /// re-alert after `repeat_interval`.
+    Alert,
+    /// The deployment recovered after previously violating.
+    Resolved,
+    /// No change worth notifying about (still OK, or still violating and within
+    /// the repeat interval).
+    Unchanged,
+}
+
+impl ViolationState {
+    /// Create a tracker. With a `repeat_interval`, a still-unresolved violation
+    /// re-alerts only after that much time has elapsed.
+    pub fn new(repeat_interval: Option<Duration>) -> Self {
+        Self {
+            active: HashMap::new(),
+            repeat_interval,
+        }
+    }
+
+    /// Record the observed status of a deployment and decide what to notify.
+    pub fn observe(&mut self, namespace: &str, name: &str, violating: bool) -> Transition {
+        self.observe_at(namespace, name, violating, Instant::now())
+    }
+
+    /// Clear any tracked violation for a deleted deployment. Returns whether it
+    /// had been violating, so the caller can optionally emit a resolved notice.
+    pub fn clear(&mut self, namespace: &str, name: &str) -> bool {
+        self.active
+            .remove(&(namespace.to_string(), name.to_string()))
+            .is_some()
+    }
+
+    fn observe_at(
+        &mut self,
+        namespace: &str,
+        name: &str,
+        violating: bool,
+        now: Instant,
+    ) -> Transition {
+        let key = (namespace.to_string(), name.to_string());
+        match (violating, self.active.get(&key).copied()) {
+            // OK -> violating: first alert.
+            (true, None) => {
+                self.active.insert(key, now);
+                Transition::Alert
+            }
+            // Still violating: re-alert only once the repeat interval elapses.
+            (true, Some(since)) => match self.repeat_interval {
+                Some(interval) if now.duration_since(since) >= interval => {
+                    self.active.insert(key, now);
+                    Transition::Alert
+                }
+                _ => Transition::Unchanged,
+            },
+            // violating -> OK: recovery.
+            (false, Some(_)) => {
+                self.active.remove(&key);
+                Transition::Resolved
+            }
+            // Still OK.
+            (false, None) => Transition::Unchanged,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_violation_alerts_then_dedups() {
+        let mut state = ViolationState::new(None);
+        assert_eq!(state.observe("ns", "a", true), Transition::Alert);
+        assert_eq!(state.observe("ns", "a", true), Transition::Unchanged);
+    }
+
+    #[test]
+    fn recovery_resolves_once() {
+        let mut state = ViolationState::new(None);
+        state.observe("ns", "a", true);
+        assert_eq!(state.observe("ns", "a", false), Transition::Resolved);
+        assert_eq!(state.observe("ns", "a", false), Transition::Unchanged);
+    }
+
+    #[test]
+    fn never_violated_stays_unchanged() {
+        let mut state = ViolationState::new(None);
+        assert_eq!(state.observe("ns", "a", false), Transition::Unchanged);
+    }
+
+    #[test]
+    fn repeat_interval_re_alerts_only_after_elapsing() {
+        let interval = Duration::from_secs(60);
+        let mut state = ViolationState::new(Some(interval));
+        let start = Instant::now();
+        assert_eq!(state.observe_at("ns", "a", true, start), Transition::Alert);
+        assert_eq!(
+            state.observe_at("ns", "a", true, start + Duration::from_secs(30)),
+            Transition::Unchanged
+        );
+        assert_eq!(
+            state.observe_at("ns", "a", true, start + Duration::from_secs(90)),
+            Transition::Alert
+        );
+    }
+
+    #[test]
+    fn clear_reports_prior_violation_then_forgets() {
+        let mut state = ViolationState::new(None);
+        state.observe("ns", "a", true);
+        assert!(state.clear("ns", "a"));
+        assert!(!state.clear("ns", "a"));
+    }
+}
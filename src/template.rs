@@ -0,0 +1,91 @@
+use anyhow::{Context, Result};
+use tera::Tera;
+
+use crate::notify::ViolationEvent;
+
+const ALERT_NAME: &str = "alert";
+const BATCH_NAME: &str = "batch";
+const RESOLVED_NAME: &str = "resolved";
+
+/// Built-in templates. Defaults stay close to the original strings; failing
+/// policy descriptions are appended when present.
+const DEFAULT_ALERT: &str = "⚠️ Deployment failed policy checks\nenv: {{ env }}\nname: {{ name }}{% for p in policies %}\n• {{ p }}{% endfor %}";
+const DEFAULT_RESOLVED: &str =
+    "✅ Resolved: policy checks passing\nenv: {{ env }}\nname: {{ name }}";
+const DEFAULT_BATCH: &str = "⚠️ Found {{ count }} deployment(s) failing policy checks\nenv: {{ env }}\n{% for v in violations %}• {{ v.namespace }}/{{ v.name }}{% if not loop.last %}\n{% endif %}{% endfor %}";
+
+/// Per-message template overrides, loaded from config. Each unset field falls
+/// back to the matching built-in default.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct TemplateConfig {
+    pub alert: Option<String>,
+    pub batch: Option<String>,
+    pub resolved: Option<String>,
+}
+
+/// Renders the alert, batch and resolved messages from configurable templates.
+pub struct Templates {
+    tera: Tera,
+}
+
+impl Templates {
+    /// Build the template set, overlaying any configured overrides on top of
+    /// the built-in defaults.
+    pub fn new(config: &TemplateConfig) -> Result<Self> {
+        let mut tera = Tera::default();
+        tera.add_raw_templates(vec![
+            (ALERT_NAME, config.alert.as_deref().unwrap_or(DEFAULT_ALERT)),
+            (BATCH_NAME, config.batch.as_deref().unwrap_or(DEFAULT_BATCH)),
+            (
+                RESOLVED_NAME,
+                config.resolved.as_deref().unwrap_or(DEFAULT_RESOLVED),
+            ),
+        ])
+        .context("compiling notification templates")?;
+        Ok(Self { tera })
+    }
+
+    pub fn render_alert(&self, event: &ViolationEvent) -> Result<String> {
+        self.render(ALERT_NAME, &event_context(event))
+    }
+
+    pub fn render_resolved(&self, event: &ViolationEvent) -> Result<String> {
+        self.render(RESOLVED_NAME, &event_context(event))
+    }
+
+    pub fn render_batch(&self, env: &str, violations: &[ViolationEvent]) -> Result<String> {
+        let mut ctx = tera::Context::new();
+        ctx.insert("env", env);
+        ctx.insert("count", &violations.len());
+        let list: Vec<_> = violations.iter().map(event_map).collect();
+        ctx.insert("violations", &list);
+        self.render(BATCH_NAME, &ctx)
+    }
+
+    fn render(&self, name: &str, ctx: &tera::Context) -> Result<String> {
+        self.tera
+            .render(name, ctx)
+            .with_context(|| format!("rendering '{}' template", name))
+    }
+}
+
+fn event_context(event: &ViolationEvent) -> tera::Context {
+    let mut ctx = tera::Context::new();
+    ctx.insert("namespace", &event.namespace);
+    ctx.insert("name", &event.name);
+    ctx.insert("env", &event.env);
+    ctx.insert("labels", &event.labels);
+    ctx.insert("policies", &event.policies);
+    ctx
+}
+
+fn event_map(event: &ViolationEvent) -> serde_json::Value {
+    serde_json::json!({
+        "namespace": event.namespace,
+        "name": event.name,
+        "env": event.env,
+        "labels": event.labels,
+        "policies": event.policies,
+    })
+}